@@ -0,0 +1,100 @@
+//! Build script for the Sprocket command line tool.
+//!
+//! Records the versions of the `wdl` component crates from `Cargo.lock` so
+//! that they can be reported by `sprocket version`.
+//!
+//! Cargo writes the lock file it resolved dependencies with before running
+//! build scripts. The lock file is found in the package directory or, when
+//! Sprocket is built as part of a workspace, the nearest ancestor directory.
+//! Note that `cargo install` only builds with the published lock file when
+//! `--locked` is specified.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// The name of the environment variable the component versions are stored in.
+const COMPONENT_VERSIONS_VAR: &str = "SPROCKET_COMPONENT_VERSIONS";
+
+/// Parses the `wdl` component crate versions from the contents of a
+/// `Cargo.lock` file.
+///
+/// Returns a list of `name=version` pairs.
+fn parse_component_versions(lock: &str) -> Vec<String> {
+    let mut versions = Vec::new();
+    let mut name = None;
+    for line in lock.lines() {
+        let line = line.trim();
+        if line == "[[package]]" {
+            name = None;
+        } else if let Some(value) = line.strip_prefix("name = ") {
+            name = Some(value.trim_matches('"'));
+        } else if let Some(value) = line.strip_prefix("version = ") {
+            if let Some(name) = name.take().filter(|n| *n == "wdl" || n.starts_with("wdl-")) {
+                versions.push(format!(
+                    "{name}={version}",
+                    version = value.trim_matches('"')
+                ));
+            }
+        }
+    }
+
+    // Sort by name only so that `wdl` precedes `wdl-*`
+    versions.sort_by(|a, b| a.split('=').next().cmp(&b.split('=').next()));
+    versions
+}
+
+/// Finds the `Cargo.lock` file for a package in the given directory.
+fn find_lock_file(manifest_dir: &Path) -> Option<PathBuf> {
+    manifest_dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.is_file())
+}
+
+fn main() {
+    let manifest_dir =
+        PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("manifest directory should be set"));
+
+    let versions = match find_lock_file(&manifest_dir) {
+        Some(lock_path) => {
+            println!("cargo:rerun-if-changed={path}", path = lock_path.display());
+            match fs::read_to_string(&lock_path) {
+                Ok(lock) => {
+                    let versions = parse_component_versions(&lock);
+                    if versions.is_empty() {
+                        println!(
+                            "cargo:warning=`{path}` does not contain any `wdl` crates: \
+                             `sprocket version` will not report component versions",
+                            path = lock_path.display()
+                        );
+                    }
+
+                    versions
+                }
+                Err(e) => {
+                    println!(
+                        "cargo:warning=failed to read `{path}`: {e}: `sprocket version` will not \
+                         report component versions",
+                        path = lock_path.display()
+                    );
+                    Vec::new()
+                }
+            }
+        }
+        None => {
+            println!(
+                "cargo:warning=failed to find `Cargo.lock` in `{path}` or its ancestors: \
+                 `sprocket version` will not report component versions",
+                path = manifest_dir.display()
+            );
+            Vec::new()
+        }
+    };
+
+    println!(
+        "cargo:rustc-env={COMPONENT_VERSIONS_VAR}={versions}",
+        versions = versions.join(",")
+    );
+}
@@ -0,0 +1,280 @@
+//! Baselines of known diagnostics.
+//!
+//! A baseline records the diagnostics reported for a set of documents so that
+//! subsequent checks only report diagnostics that are not in the baseline.
+//!
+//! Diagnostics are keyed by the file they were reported in, the rule that
+//! reported them, their message, and the source text they highlight. The line
+//! and column of a diagnostic are intentionally not part of the key so that
+//! unrelated edits to a document do not invalidate its baseline.
+//!
+//! Local files are keyed by their path relative to the directory containing
+//! the baseline file so that a baseline can be shared between checkouts.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+use url::Url;
+use wdl::analysis::path_to_uri;
+use wdl::ast::Diagnostic;
+
+/// The current version of the baseline file format.
+const VERSION: u32 = 1;
+
+/// Represents the key of a diagnostic in a baseline.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Entry {
+    /// The file the diagnostic was reported in.
+    file: String,
+    /// The rule that reported the diagnostic, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rule: Option<String>,
+    /// The message of the diagnostic.
+    message: String,
+    /// The whitespace-normalized source text highlighted by the diagnostic.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    snippet: String,
+}
+
+impl Entry {
+    /// Creates a new baseline entry for the given diagnostic.
+    fn new(file: &str, diagnostic: &Diagnostic, source: &str) -> Self {
+        let snippet = diagnostic
+            .labels()
+            .next()
+            .and_then(|l| {
+                let span = l.span();
+                source.get(span.start()..span.end())
+            })
+            .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default();
+
+        Self {
+            file: file.to_string(),
+            rule: diagnostic.rule().map(ToString::to_string),
+            message: diagnostic.message().to_string(),
+            snippet,
+        }
+    }
+}
+
+/// The serialized representation of a baseline.
+#[derive(Debug, Serialize, Deserialize)]
+struct File {
+    /// The version of the baseline file format.
+    version: u32,
+    /// The entries in the baseline.
+    diagnostics: Vec<Entry>,
+}
+
+/// Represents a baseline of known diagnostics.
+///
+/// A diagnostic that is reported more times than it appears in the baseline is
+/// still reported for the additional occurrences.
+#[derive(Debug)]
+pub struct Baseline {
+    /// The directory that local file keys are relative to.
+    root: PathBuf,
+    /// The entries in the baseline with their number of occurrences.
+    entries: BTreeMap<Entry, usize>,
+}
+
+impl Baseline {
+    /// Creates an empty baseline that will be written to the given path.
+    pub fn new(path: &Path) -> Result<Self> {
+        let dir = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        // Normalize the directory the same way analysis normalizes document paths
+        let root = path_to_uri(dir)
+            .and_then(|uri| uri.to_file_path().ok())
+            .with_context(|| {
+                format!(
+                    "failed to determine the absolute path of baseline directory `{dir}`",
+                    dir = dir.display()
+                )
+            })?;
+
+        Ok(Self {
+            root,
+            entries: Default::default(),
+        })
+    }
+
+    /// Reads a baseline from the given path.
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read baseline `{path}`", path = path.display()))?;
+        let file: File = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse baseline `{path}`", path = path.display()))?;
+
+        if file.version != VERSION {
+            anyhow::bail!(
+                "baseline `{path}` has unsupported version {version}",
+                path = path.display(),
+                version = file.version
+            );
+        }
+
+        let mut baseline = Self::new(path)?;
+        for entry in file.diagnostics {
+            *baseline.entries.entry(entry).or_default() += 1;
+        }
+
+        Ok(baseline)
+    }
+
+    /// Writes the baseline to the given path.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let file = File {
+            version: VERSION,
+            diagnostics: self
+                .entries
+                .iter()
+                .flat_map(|(entry, count)| std::iter::repeat_n(entry.clone(), *count))
+                .collect(),
+        };
+
+        let mut contents =
+            serde_json::to_string_pretty(&file).context("failed to serialize baseline")?;
+        contents.push('\n');
+        fs::write(path, contents)
+            .with_context(|| format!("failed to write baseline `{path}`", path = path.display()))
+    }
+
+    /// Gets the number of diagnostics in the baseline.
+    pub fn len(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    /// Determines if the baseline is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the key identifying the document with the given URI in the
+    /// baseline.
+    ///
+    /// Local files under the baseline's directory are keyed by their relative
+    /// path using `/` separators; all other documents are keyed by their path
+    /// or URI.
+    pub fn file_key(&self, uri: &Url) -> String {
+        if uri.scheme() != "file" {
+            return uri.to_string();
+        }
+
+        match uri.to_file_path() {
+            Ok(path) => match path.strip_prefix(&self.root) {
+                Ok(relative) => relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                Err(_) => path.to_string_lossy().into_owned(),
+            },
+            Err(_) => uri.to_string(),
+        }
+    }
+
+    /// Adds a diagnostic reported in the given file to the baseline.
+    ///
+    /// The file should be a key returned from [`Baseline::file_key`].
+    pub fn insert(&mut self, file: &str, diagnostic: &Diagnostic, source: &str) {
+        *self
+            .entries
+            .entry(Entry::new(file, diagnostic, source))
+            .or_default() += 1;
+    }
+
+    /// Consumes a matching occurrence of the diagnostic from the baseline.
+    ///
+    /// Returns `true` if the diagnostic is known and should be suppressed.
+    pub fn suppress(&mut self, file: &str, diagnostic: &Diagnostic, source: &str) -> bool {
+        let entry = Entry::new(file, diagnostic, source);
+        match self.entries.get_mut(&entry) {
+            Some(count) => {
+                *count -= 1;
+                if *count == 0 {
+                    self.entries.remove(&entry);
+                }
+
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wdl::ast::Span;
+
+    use super::*;
+
+    /// The source of the document the test diagnostics are reported in.
+    const SOURCE: &str = "version 1.1\n\nworkflow test {}\n";
+
+    /// Creates a diagnostic for the given rule highlighting `test`.
+    fn diagnostic(rule: &str, message: &str) -> Diagnostic {
+        Diagnostic::warning(message)
+            .with_rule(rule)
+            .with_highlight(Span::new(SOURCE.find("test").unwrap(), 4))
+    }
+
+    #[test]
+    fn suppresses_known_diagnostics() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        let path = dir.path().join("baseline.json");
+
+        let known = diagnostic("KnownRule", "known issue");
+        let mut baseline = Baseline::new(&path).expect("should create baseline");
+        baseline.insert("a.wdl", &known, SOURCE);
+        baseline.write(&path).expect("should write baseline");
+
+        let mut baseline = Baseline::read(&path).expect("should read baseline");
+        assert_eq!(baseline.len(), 1);
+
+        // A new diagnostic is still reported
+        let new = diagnostic("NewRule", "new issue");
+        assert!(!baseline.suppress("a.wdl", &new, SOURCE));
+
+        // The same diagnostic in a different file is still reported
+        assert!(!baseline.suppress("b.wdl", &known, SOURCE));
+
+        // The known diagnostic is suppressed, even after the document moves
+        let moved = format!("# A new comment\n{SOURCE}");
+        let known = Diagnostic::warning("known issue")
+            .with_rule("KnownRule")
+            .with_highlight(Span::new(moved.find("test").unwrap(), 4));
+        assert!(baseline.suppress("a.wdl", &known, &moved));
+
+        // Each baselined occurrence only suppresses one diagnostic
+        assert!(!baseline.suppress("a.wdl", &known, &moved));
+        assert!(baseline.is_empty());
+    }
+
+    #[test]
+    fn file_keys_are_relative_to_the_baseline() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        let baseline =
+            Baseline::new(&dir.path().join("baseline.json")).expect("should create baseline");
+
+        let uri = path_to_uri(dir.path().join("dir").join("a.wdl")).expect("should be a URL");
+        assert_eq!(baseline.file_key(&uri), "dir/a.wdl");
+
+        // The baseline's directory is normalized like the analyzed documents
+        let baseline = Baseline::new(&dir.path().join("dir").join("..").join("baseline.json"))
+            .expect("should create baseline");
+        assert_eq!(baseline.file_key(&uri), "dir/a.wdl");
+
+        let uri = Url::parse("https://example.com/a.wdl").expect("should be a URL");
+        assert_eq!(baseline.file_key(&uri), "https://example.com/a.wdl");
+    }
+}
@@ -4,4 +4,6 @@ pub mod analyzer;
 pub mod check;
 pub mod explain;
 pub mod format;
+pub mod inputs;
 pub mod validate;
+pub mod version;
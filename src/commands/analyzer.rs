@@ -1,6 +1,15 @@
 //! Implementation of the analyzer command.
 
+use anyhow::Result;
+use anyhow::bail;
 use clap::Parser;
+use indexmap::IndexMap;
+use serde_json::Value;
+use serde_json::json;
+use wdl::analysis::document::Document;
+use wdl::analysis::document::Input;
+use wdl::analysis::document::Output;
+use wdl::cli::analyze;
 use wdl::lsp::Server;
 use wdl::lsp::ServerOptions;
 
@@ -9,16 +18,129 @@ use wdl::lsp::ServerOptions;
 #[command(author, version, about)]
 pub struct AnalyzerArgs {
     /// Use stdin and stdout for the RPC transport.
-    #[clap(long, required = true)]
+    #[clap(long, required_unless_present = "dump", conflicts_with = "dump")]
     pub stdio: bool,
 
     /// Whether or not to enable all lint rules.
-    #[clap(long)]
+    #[clap(long, conflicts_with = "dump")]
     pub lint: bool,
+
+    /// Prints the resolved document model of the given document as JSON
+    /// instead of running the LSP server.
+    ///
+    /// The output includes the document, every document it imports, and the
+    /// tasks, workflows, and structs each of them declares.
+    #[clap(long, value_name = "PATH or URL")]
+    pub dump: Option<String>,
+}
+
+/// Converts the inputs of a task or workflow to JSON.
+fn inputs_to_json(inputs: &IndexMap<String, Input>) -> Value {
+    inputs
+        .iter()
+        .map(|(name, input)| {
+            (
+                name.clone(),
+                json!({
+                    "type": input.ty().to_string(),
+                    "required": input.required(),
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+/// Converts the outputs of a task or workflow to JSON.
+fn outputs_to_json(outputs: &IndexMap<String, Output>) -> Value {
+    outputs
+        .iter()
+        .map(|(name, output)| (name.clone(), json!({ "type": output.ty().to_string() })))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+/// Converts the resolved model of an analyzed document to JSON.
+fn document_to_json(document: &Document) -> Value {
+    let imports = document
+        .namespaces()
+        .map(|(name, ns)| (name.to_string(), Value::from(ns.source().as_str())))
+        .collect::<serde_json::Map<_, _>>();
+
+    let structs = document
+        .structs()
+        .map(|(name, _)| Value::from(name))
+        .collect::<Vec<_>>();
+
+    let tasks = document
+        .tasks()
+        .map(|task| {
+            (
+                task.name().to_string(),
+                json!({
+                    "inputs": inputs_to_json(task.inputs()),
+                    "outputs": outputs_to_json(task.outputs()),
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    let workflow = document.workflow().map(|workflow| {
+        json!({
+            "name": workflow.name(),
+            "inputs": inputs_to_json(workflow.inputs()),
+            "outputs": outputs_to_json(workflow.outputs()),
+        })
+    });
+
+    json!({
+        "uri": document.uri().as_str(),
+        "version": document.version().map(|v| v.to_string()),
+        "imports": imports,
+        "structs": structs,
+        "tasks": tasks,
+        "workflow": workflow,
+    })
+}
+
+/// Gets the resolved document model of the given document as JSON.
+///
+/// Documents are ordered by URI so that the output is stable.
+async fn dump_json(file: &str) -> Result<Value> {
+    let mut results = analyze(file, Vec::new(), false, false).await?;
+    results.sort_by(|a, b| a.document().uri().cmp(b.document().uri()));
+
+    let mut documents = Vec::with_capacity(results.len());
+    for result in &results {
+        if let Some(e) = result.error() {
+            bail!(
+                "failed to analyze `{uri}`: {e:#}",
+                uri = result.document().uri()
+            );
+        }
+
+        documents.push(document_to_json(result.document()));
+    }
+
+    Ok(json!({ "documents": documents }))
+}
+
+/// Prints the resolved document model of the given document as JSON.
+async fn dump(file: &str) -> Result<()> {
+    println!(
+        "{json}",
+        json = serde_json::to_string_pretty(&dump_json(file).await?)?
+    );
+
+    Ok(())
 }
 
 /// Runs the `analyzer` command.
 pub async fn analyzer(args: AnalyzerArgs) -> anyhow::Result<()> {
+    if let Some(file) = &args.dump {
+        return dump(file).await;
+    }
+
     Server::run(ServerOptions {
         name: Some("Sprocket".into()),
         version: Some(env!("CARGO_PKG_VERSION").into()),
@@ -26,3 +148,57 @@ pub async fn analyzer(args: AnalyzerArgs) -> anyhow::Result<()> {
     })
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn dump_document() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        std::fs::write(
+            dir.path().join("lib.wdl"),
+            "version 1.1\n\nstruct Sample {\n    String name\n}\n\ntask greet {\n    input {\n        \
+             Sample sample\n    }\n\n    command <<<>>>\n\n    output {\n        String \
+             greeting = \"hello\"\n    }\n}\n",
+        )
+        .expect("should write document");
+        let path = dir.path().join("main.wdl");
+        std::fs::write(
+            &path,
+            "version 1.1\n\nimport \"lib.wdl\"\n\nworkflow test {\n    input {\n        \
+             Array[String]+ names\n        Int? count\n    }\n}\n",
+        )
+        .expect("should write document");
+
+        let json = dump_json(path.to_str().unwrap())
+            .await
+            .expect("should dump document");
+        let documents = json["documents"].as_array().expect("should be an array");
+        assert_eq!(documents.len(), 2);
+
+        // Documents are ordered by URI
+        let (lib, main) = (&documents[0], &documents[1]);
+        assert!(lib["uri"].as_str().unwrap().ends_with("lib.wdl"));
+        assert_eq!(lib["structs"], json!(["Sample"]));
+        assert_eq!(
+            lib["tasks"]["greet"]["inputs"]["sample"],
+            json!({ "type": "Sample", "required": true })
+        );
+        assert_eq!(
+            lib["tasks"]["greet"]["outputs"]["greeting"],
+            json!({ "type": "String" })
+        );
+
+        assert_eq!(main["version"], "1.1");
+        assert_eq!(main["imports"]["lib"], lib["uri"]);
+        assert_eq!(main["workflow"]["name"], "test");
+        assert_eq!(
+            main["workflow"]["inputs"],
+            json!({
+                "names": { "type": "Array[String]+", "required": true },
+                "count": { "type": "Int?", "required": false },
+            })
+        );
+    }
+}
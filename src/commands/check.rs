@@ -1,26 +1,51 @@
 //! Implementation of the check and lint commands.
 
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::bail;
 use clap::Parser;
+use clap::ValueEnum;
+use colored::Color;
+use colored::Colorize;
 use url::Url;
+use wdl::analysis::path_to_uri;
 use wdl::ast::Diagnostic;
 use wdl::ast::Severity;
 use wdl::cli::analyze;
 
 use crate::Mode;
+use crate::STDIN_NAME;
+use crate::STDIN_PATH;
+use crate::StdinDocument;
+use crate::baseline::Baseline;
 use crate::emit_diagnostics;
+use crate::is_staged_stdin_document;
 
 /// Common arguments for the `check` and `lint` subcommands.
 #[derive(Parser, Debug)]
 #[command(author, version, about)]
 pub struct Common {
-    /// The file, URL, or directory to check.
-    #[arg(required = true)]
+    /// The file, URL, or directory to check (`-` for STDIN).
+    ///
+    /// Relative imports in a document read from STDIN are resolved against
+    /// the directory of `--stdin-filepath` or, if it is not specified, a
+    /// temporary directory.
+    #[arg(required_unless_present = "list")]
     #[clap(value_name = "PATH or URL")]
-    pub file: String,
+    pub file: Option<String>,
+
+    /// The path of the document being read from STDIN.
+    ///
+    /// The path is used when reporting diagnostics and to resolve relative
+    /// imports; the source is still read from STDIN. To resolve imports, the
+    /// source is written to a hidden `.sprocket-stdin-*.wdl` file in the
+    /// path's directory that is removed on exit; directory walks skip such
+    /// files if they are left behind.
+    #[arg(long, value_name = "PATH")]
+    pub stdin_filepath: Option<PathBuf>,
 
     /// A single rule ID to except from running.
     ///
@@ -28,8 +53,15 @@ pub struct Common {
     #[arg(short, long, value_name = "RULE")]
     pub except: Vec<String>,
 
+    /// Lists the rules that would run and whether each is enabled instead of
+    /// checking any files.
+    #[arg(long, conflicts_with = "file")]
+    pub list: bool,
+
     /// Causes the command to fail if warnings were reported.
-    #[clap(long)]
+    ///
+    /// By default, only errors cause the command to fail.
+    #[clap(long, visible_alias = "warnings-as-errors")]
     pub deny_warnings: bool,
 
     /// Causes the command to fail if notes were reported.
@@ -66,6 +98,74 @@ pub struct Common {
     /// The report mode.
     #[arg(short = 'm', long, default_value_t, value_name = "MODE")]
     pub report_mode: Mode,
+
+    /// Only print the summary line instead of every diagnostic.
+    ///
+    /// This is also enabled by the global `--quiet` flag.
+    #[arg(long)]
+    pub summary_only: bool,
+
+    /// Suppress the known diagnostics recorded in the given baseline file.
+    ///
+    /// Diagnostics not present in the baseline are still reported.
+    #[arg(long, value_name = "FILE", conflicts_with = "write_baseline")]
+    pub baseline: Option<PathBuf>,
+
+    /// Record the reported diagnostics to the given baseline file.
+    ///
+    /// Local files are recorded relative to the directory containing the
+    /// baseline file so that the baseline can be shared between checkouts.
+    /// The command succeeds regardless of the diagnostics reported.
+    #[arg(long, value_name = "FILE")]
+    pub write_baseline: Option<PathBuf>,
+
+    /// The maximum number of diagnostics to print.
+    ///
+    /// When diagnostics must be dropped, errors are kept before warnings and
+    /// notes; the diagnostics that are kept are still printed file by file.
+    /// Diagnostics that are not printed are still counted in the summary.
+    #[arg(long, value_name = "N")]
+    pub max_diagnostics: Option<usize>,
+
+    /// Whether `--max-diagnostics` applies to each file or to all files.
+    #[arg(
+        long,
+        default_value_t,
+        value_name = "SCOPE",
+        requires = "max_diagnostics"
+    )]
+    pub max_diagnostics_scope: LimitScope,
+}
+
+/// The scope that a limit on the number of printed diagnostics applies to.
+#[derive(Clone, Copy, Debug, Default, ValueEnum, PartialEq, Eq)]
+pub enum LimitScope {
+    /// The limit applies to the diagnostics of all files.
+    #[default]
+    Total,
+
+    /// The limit applies to the diagnostics of each file.
+    File,
+}
+
+impl std::fmt::Display for LimitScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitScope::Total => write!(f, "total"),
+            LimitScope::File => write!(f, "file"),
+        }
+    }
+}
+
+/// The diagnostics to report for a single file.
+#[derive(Debug)]
+struct FileReport {
+    /// The URI or path of the file to display.
+    uri: String,
+    /// The source of the file.
+    source: String,
+    /// The diagnostics of the file, ordered by severity.
+    diagnostics: Vec<Diagnostic>,
 }
 
 /// Arguments for the `check` subcommand.
@@ -90,6 +190,230 @@ pub struct LintArgs {
     pub common: Common,
 }
 
+/// Gets the rank of a severity used to order diagnostics within a file.
+///
+/// Lower ranks are emitted first.
+fn severity_rank(severity: Severity) -> u8 {
+    match severity {
+        Severity::Error => 0,
+        Severity::Warning => 1,
+        Severity::Note => 2,
+    }
+}
+
+/// Determines the number of diagnostics to print for each report.
+///
+/// When the limit applies to all files, errors from every file are kept before
+/// any warnings, and warnings before any notes. Each report's diagnostics are
+/// sorted by severity, so its limit always keeps the most severe ones.
+fn limit_diagnostics(reports: &[FileReport], max: Option<usize>, scope: LimitScope) -> Vec<usize> {
+    let Some(max) = max else {
+        return reports.iter().map(|r| r.diagnostics.len()).collect();
+    };
+
+    match scope {
+        LimitScope::File => reports
+            .iter()
+            .map(|r| r.diagnostics.len().min(max))
+            .collect(),
+        LimitScope::Total => {
+            let mut limits = vec![0; reports.len()];
+            let mut remaining = max;
+            for severity in [Severity::Error, Severity::Warning, Severity::Note] {
+                for (report, limit) in reports.iter().zip(limits.iter_mut()) {
+                    let count = report
+                        .diagnostics
+                        .iter()
+                        .filter(|d| d.severity() == severity)
+                        .count()
+                        .min(remaining);
+                    *limit += count;
+                    remaining -= count;
+                }
+            }
+
+            limits
+        }
+    }
+}
+
+/// Counts the diagnostics that are not printed because of the given limits.
+fn hidden_diagnostics(reports: &[FileReport], limits: &[usize]) -> usize {
+    reports
+        .iter()
+        .zip(limits)
+        .map(|(report, limit)| report.diagnostics.len() - limit)
+        .sum()
+}
+
+/// A summary of the diagnostics reported for the checked files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Summary {
+    /// The number of errors reported.
+    errors: usize,
+    /// The number of warnings reported.
+    warnings: usize,
+    /// The number of notes reported.
+    notes: usize,
+    /// The number of files checked.
+    files: usize,
+}
+
+impl Summary {
+    /// Creates a summary of the given reports.
+    ///
+    /// The `files` argument is the number of files checked, including those
+    /// without diagnostics.
+    fn new(reports: &[FileReport], files: usize) -> Self {
+        let mut summary = Self {
+            files,
+            ..Default::default()
+        };
+
+        for diagnostic in reports.iter().flat_map(|r| &r.diagnostics) {
+            match diagnostic.severity() {
+                Severity::Error => summary.errors += 1,
+                Severity::Warning => summary.warnings += 1,
+                Severity::Note => summary.notes += 1,
+            }
+        }
+
+        summary
+    }
+
+    /// Determines why the command should fail, if it should.
+    ///
+    /// Errors always cause a failure; warnings and notes only cause a failure
+    /// if they are denied.
+    fn failure(&self, deny_warnings: bool, deny_notes: bool) -> Option<String> {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        if self.errors > 0 {
+            Some(format!(
+                "failing due to {count} error{s}",
+                count = self.errors,
+                s = plural(self.errors)
+            ))
+        } else if deny_warnings && self.warnings > 0 {
+            Some(format!(
+                "failing due to {count} warning{s} (`--deny-warnings` was specified)",
+                count = self.warnings,
+                s = plural(self.warnings)
+            ))
+        } else if deny_notes && self.notes > 0 {
+            Some(format!(
+                "failing due to {count} note{s} (`--deny-notes` was specified)",
+                count = self.notes,
+                s = plural(self.notes)
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Renders the summary as a single line.
+    fn render(&self, no_color: bool) -> String {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        let colorize = |text: String, count: usize, color: Color| {
+            if no_color || count == 0 {
+                text.normal()
+            } else {
+                text.color(color).bold()
+            }
+        };
+
+        format!(
+            "{errors}, {warnings}, {notes} across {files} file{s}",
+            errors = colorize(
+                format!(
+                    "{count} error{s}",
+                    count = self.errors,
+                    s = plural(self.errors)
+                ),
+                self.errors,
+                Color::Red
+            ),
+            warnings = colorize(
+                format!(
+                    "{count} warning{s}",
+                    count = self.warnings,
+                    s = plural(self.warnings)
+                ),
+                self.warnings,
+                Color::Yellow
+            ),
+            notes = colorize(
+                format!(
+                    "{count} note{s}",
+                    count = self.notes,
+                    s = plural(self.notes)
+                ),
+                self.notes,
+                Color::Cyan
+            ),
+            files = self.files,
+            s = plural(self.files),
+        )
+    }
+}
+
+/// Renders the analysis and lint rules, grouped by tag, and whether each rule
+/// is enabled given the exceptions and whether linting is enabled.
+fn rule_listing(exceptions: &[String], lint: bool, no_color: bool) -> String {
+    let state = |id: &str, enabled: bool| {
+        let (text, color) = if exceptions.iter().any(|e| e == id) {
+            ("disabled (overridden by `--except`)", Some(Color::Red))
+        } else if enabled {
+            ("enabled", Some(Color::Green))
+        } else {
+            ("disabled (linting is not enabled)", None)
+        };
+
+        match color {
+            Some(color) if !no_color => text.color(color),
+            _ => text.normal(),
+        }
+    };
+    let header = |text: String| {
+        if no_color {
+            text.normal()
+        } else {
+            text.bold().underline()
+        }
+    };
+
+    let mut listing = format!("{header}\n", header = header("Analysis rules".to_string()));
+    let mut analysis_rules = wdl::analysis::rules()
+        .iter()
+        .map(|r| r.id().to_string())
+        .collect::<Vec<_>>();
+    analysis_rules.sort();
+    for id in &analysis_rules {
+        listing.push_str(&format!("  {id} {state}\n", state = state(id, true)));
+    }
+
+    let mut tags: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for rule in wdl::lint::rules() {
+        for tag in rule.tags().iter() {
+            tags.entry(tag.to_string())
+                .or_default()
+                .push(rule.id().to_string());
+        }
+    }
+
+    for (tag, mut ids) in tags {
+        ids.sort();
+        listing.push_str(&format!(
+            "\n{header}\n",
+            header = header(format!("Lint rules tagged `{tag}`"))
+        ));
+        for id in &ids {
+            listing.push_str(&format!("  {id} {state}\n", state = state(id, lint)));
+        }
+    }
+
+    listing
+}
+
 /// Checks WDL source files for diagnostics.
 pub async fn check(args: CheckArgs) -> anyhow::Result<()> {
     if args.common.shellcheck && !args.lint {
@@ -100,7 +424,30 @@ pub async fn check(args: CheckArgs) -> anyhow::Result<()> {
     let lint = args.lint;
     let shellcheck = args.common.shellcheck;
 
-    let file = args.common.file;
+    if args.common.list {
+        print!(
+            "{listing}",
+            listing = rule_listing(&exceptions, lint, args.common.no_color)
+        );
+        return Ok(());
+    }
+
+    let file = args
+        .common
+        .file
+        .expect("file should be present unless `--list` was specified");
+    let stdin = if file == STDIN_PATH {
+        Some(StdinDocument::read(args.common.stdin_filepath.as_deref())?)
+    } else if args.common.stdin_filepath.is_some() {
+        bail!("`--stdin-filepath` requires the document to be read from STDIN");
+    } else {
+        None
+    };
+
+    let file = match &stdin {
+        Some(stdin) => stdin.path().to_string_lossy().into_owned(),
+        None => file,
+    };
 
     if args.common.single_document
         && fs::metadata(&file)
@@ -116,12 +463,24 @@ pub async fn check(args: CheckArgs) -> anyhow::Result<()> {
 
     let remote_file = Url::parse(&file).is_ok();
 
+    let mut baseline = args
+        .common
+        .baseline
+        .as_deref()
+        .map(Baseline::read)
+        .transpose()?;
+    let mut new_baseline = args
+        .common
+        .write_baseline
+        .as_deref()
+        .map(Baseline::new)
+        .transpose()?;
+
     let results = analyze(&file, exceptions, lint, shellcheck).await?;
 
     let cwd = std::env::current_dir().ok();
-    let mut error_count = 0;
-    let mut warning_count = 0;
-    let mut note_count = 0;
+    let mut reports = Vec::new();
+    let mut checked_files = 0;
     for result in &results {
         let mut suppress = false;
 
@@ -130,9 +489,32 @@ pub async fn check(args: CheckArgs) -> anyhow::Result<()> {
         if args.common.single_document && !uri.as_str().contains(&file) {
             continue;
         }
+
+        // Skip documents from STDIN that were left behind by other invocations
+        let path = uri.to_file_path().ok();
+        let stdin_document = stdin.as_ref().filter(|s| path.as_deref() == Some(s.path()));
+        if stdin_document.is_none() && path.is_some_and(|p| is_staged_stdin_document(&p)) {
+            continue;
+        }
+        checked_files += 1;
+
+        let baseline_key =
+            baseline
+                .as_ref()
+                .or(new_baseline.as_ref())
+                .map(|b| match stdin_document {
+                    Some(stdin) => stdin
+                        .original_path()
+                        .and_then(path_to_uri)
+                        .map(|uri| b.file_key(&uri))
+                        .unwrap_or_else(|| STDIN_NAME.to_string()),
+                    None => b.file_key(uri),
+                });
+
         let scheme = uri.scheme();
-        let uri = match (cwd.clone(), scheme) {
-            (Some(cwd), "file") => uri
+        let uri = match (stdin_document, cwd.clone(), scheme) {
+            (Some(stdin), ..) => stdin.name().to_string(),
+            (None, Some(cwd), "file") => uri
                 .to_string()
                 .strip_prefix(cwd.to_str().unwrap())
                 .unwrap_or(
@@ -142,7 +524,7 @@ pub async fn check(args: CheckArgs) -> anyhow::Result<()> {
                         .as_ref(),
                 )
                 .to_string(),
-            (_, "file") => uri
+            (None, _, "file") => uri
                 .to_file_path()
                 .expect("failed to convert file URI to file path")
                 .to_string_lossy()
@@ -161,42 +543,87 @@ pub async fn check(args: CheckArgs) -> anyhow::Result<()> {
         };
 
         if !diagnostics.is_empty() {
-            emit_diagnostics(
-                diagnostics
-                    .iter()
-                    .filter(|d| !suppress || d.severity() == Severity::Error),
-                &uri,
-                &result.document().node().syntax().text().to_string(),
-                args.common.report_mode,
-                args.common.no_color,
-            );
+            let source = result.document().node().syntax().text().to_string();
+            let mut diagnostics = diagnostics
+                .iter()
+                .filter(|d| !suppress || d.severity() == Severity::Error)
+                .filter(|d| {
+                    !baseline.as_mut().is_some_and(|b| {
+                        b.suppress(baseline_key.as_deref().unwrap_or(&uri), d, &source)
+                    })
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if diagnostics.is_empty() {
+                continue;
+            }
 
-            for diagnostic in diagnostics.iter() {
-                match diagnostic.severity() {
-                    Severity::Error => error_count += 1,
-                    Severity::Warning if !suppress => warning_count += 1,
-                    Severity::Note if !suppress => note_count += 1,
-                    _ => {}
+            if let Some(new_baseline) = &mut new_baseline {
+                for diagnostic in &diagnostics {
+                    new_baseline.insert(
+                        baseline_key.as_deref().unwrap_or(&uri),
+                        diagnostic,
+                        &source,
+                    );
                 }
             }
+
+            // Group the diagnostics for the file by severity, errors first
+            diagnostics.sort_by_key(|d| severity_rank(d.severity()));
+
+            reports.push(FileReport {
+                uri,
+                source,
+                diagnostics,
+            });
         }
     }
 
-    if error_count > 0 {
-        bail!(
-            "failing due to {error_count} error{s}",
-            s = if error_count == 1 { "" } else { "s" }
+    if let (Some(path), Some(new_baseline)) = (&args.common.write_baseline, new_baseline) {
+        new_baseline.write(path)?;
+        println!(
+            "recorded {count} diagnostic{s} to baseline `{path}`",
+            count = new_baseline.len(),
+            s = if new_baseline.len() == 1 { "" } else { "s" },
+            path = path.display()
         );
-    } else if args.common.deny_warnings && warning_count > 0 {
-        bail!(
-            "failing due to {warning_count} warning{s} (`--deny-warnings` was specified)",
-            s = if warning_count == 1 { "" } else { "s" }
-        );
-    } else if args.common.deny_notes && note_count > 0 {
-        bail!(
-            "failing due to {note_count} note{s} (`--deny-notes` was specified)",
-            s = if note_count == 1 { "" } else { "s" }
+        return Ok(());
+    }
+
+    let summary = Summary::new(&reports, checked_files);
+
+    if !args.common.summary_only {
+        let limits = limit_diagnostics(
+            &reports,
+            args.common.max_diagnostics,
+            args.common.max_diagnostics_scope,
         );
+
+        for (report, limit) in reports.iter().zip(&limits) {
+            emit_diagnostics(
+                report.diagnostics.iter().take(*limit),
+                &report.uri,
+                &report.source,
+                args.common.report_mode,
+                args.common.no_color,
+            );
+        }
+
+        let hidden = hidden_diagnostics(&reports, &limits);
+
+        if hidden > 0 {
+            eprintln!(
+                "... and {hidden} more diagnostic{s} not shown (`--max-diagnostics` was \
+                 specified)",
+                s = if hidden == 1 { "" } else { "s" }
+            );
+        }
+    }
+
+    eprintln!("{summary}", summary = summary.render(args.common.no_color));
+
+    if let Some(reason) = summary.failure(args.common.deny_warnings, args.common.deny_notes) {
+        bail!(reason);
     }
 
     Ok(())
@@ -210,3 +637,182 @@ pub async fn lint(args: LintArgs) -> anyhow::Result<()> {
     })
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a report for a file with the given diagnostics.
+    fn report(uri: &str, diagnostics: Vec<Diagnostic>) -> FileReport {
+        FileReport {
+            uri: uri.to_string(),
+            source: String::new(),
+            diagnostics,
+        }
+    }
+
+    #[test]
+    fn summary_counts_diagnostics() {
+        let reports = [
+            report(
+                "a.wdl",
+                vec![Diagnostic::error("first"), Diagnostic::warning("second")],
+            ),
+            report("b.wdl", vec![Diagnostic::warning("third")]),
+        ];
+
+        // Three files were checked, but only two had diagnostics
+        let summary = Summary::new(&reports, 3);
+        assert_eq!(
+            summary,
+            Summary {
+                errors: 1,
+                warnings: 2,
+                notes: 0,
+                files: 3,
+            }
+        );
+        assert_eq!(
+            summary.render(true),
+            "1 error, 2 warnings, 0 notes across 3 files"
+        );
+    }
+
+    #[test]
+    fn summary_of_clean_files() {
+        let summary = Summary::new(&[], 50);
+        assert_eq!(
+            summary.render(true),
+            "0 errors, 0 warnings, 0 notes across 50 files"
+        );
+    }
+
+    #[test]
+    fn warnings_only_fail_when_denied() {
+        let reports = [report("a.wdl", vec![Diagnostic::warning("first")])];
+        let summary = Summary::new(&reports, 1);
+        assert_eq!(summary.failure(false, false), None);
+        assert_eq!(
+            summary.failure(true, false).as_deref(),
+            Some("failing due to 1 warning (`--deny-warnings` was specified)")
+        );
+
+        // Errors fail regardless of the flags
+        let reports = [report("a.wdl", vec![Diagnostic::error("first")])];
+        let summary = Summary::new(&reports, 1);
+        assert_eq!(
+            summary.failure(false, false).as_deref(),
+            Some("failing due to 1 error")
+        );
+    }
+
+    #[test]
+    fn warnings_as_errors_alias() {
+        let args = CheckArgs::try_parse_from(["check", "--warnings-as-errors", "a.wdl"])
+            .expect("should parse arguments");
+        assert!(args.common.deny_warnings);
+
+        let args = CheckArgs::try_parse_from(["check", "a.wdl"]).expect("should parse arguments");
+        assert!(!args.common.deny_warnings);
+    }
+
+    #[test]
+    fn total_limit_keeps_errors_first() {
+        let reports = [
+            report(
+                "a.wdl",
+                vec![Diagnostic::warning("first"), Diagnostic::warning("second")],
+            ),
+            report("b.wdl", vec![Diagnostic::error("third")]),
+        ];
+
+        // The error in the second file is kept before the warnings in the first
+        let limits = limit_diagnostics(&reports, Some(2), LimitScope::Total);
+        assert_eq!(limits, [1, 1]);
+        assert_eq!(hidden_diagnostics(&reports, &limits), 1);
+
+        let limits = limit_diagnostics(&reports, Some(1), LimitScope::Total);
+        assert_eq!(limits, [0, 1]);
+        assert_eq!(hidden_diagnostics(&reports, &limits), 2);
+
+        let limits = limit_diagnostics(&reports, None, LimitScope::Total);
+        assert_eq!(limits, [2, 1]);
+        assert_eq!(hidden_diagnostics(&reports, &limits), 0);
+    }
+
+    #[test]
+    fn file_limit_applies_to_each_file() {
+        let reports = [
+            report(
+                "a.wdl",
+                vec![
+                    Diagnostic::error("first"),
+                    Diagnostic::warning("second"),
+                    Diagnostic::note("third"),
+                ],
+            ),
+            report("b.wdl", vec![Diagnostic::warning("fourth")]),
+        ];
+
+        let limits = limit_diagnostics(&reports, Some(2), LimitScope::File);
+        assert_eq!(limits, [2, 1]);
+        assert_eq!(hidden_diagnostics(&reports, &limits), 1);
+    }
+
+    #[test]
+    fn rule_listing_marks_exceptions() {
+        let exceptions = ["SnakeCase".to_string()];
+
+        let listing = rule_listing(&exceptions, true, true);
+        assert!(listing.contains("  SnakeCase disabled (overridden by `--except`)\n"));
+        assert!(listing.contains("  DoubleQuotes enabled\n"));
+        assert!(!listing.contains('\u{1b}'), "listing should not be colored");
+
+        let listing = rule_listing(&exceptions, false, true);
+        assert!(listing.contains("  SnakeCase disabled (overridden by `--except`)\n"));
+        assert!(listing.contains("  DoubleQuotes disabled (linting is not enabled)\n"));
+    }
+
+    /// A document that imports a task from `lib.wdl`.
+    const IMPORTING_SOURCE: &str =
+        "version 1.1\n\nimport \"lib.wdl\"\n\nworkflow test {\n    call lib.greet\n}\n";
+
+    /// Gets whether the staged STDIN document analyzed without errors.
+    async fn analyzes_cleanly(stdin: &StdinDocument) -> bool {
+        let results = analyze(&stdin.path().to_string_lossy(), Vec::new(), false, false)
+            .await
+            .expect("should analyze document");
+        let result = results
+            .iter()
+            .find(|r| r.document().uri().to_file_path().ok().as_deref() == Some(stdin.path()))
+            .expect("should have results for the document");
+        result.error().is_none()
+            && !result
+                .document()
+                .diagnostics()
+                .iter()
+                .any(|d| d.severity() == Severity::Error)
+    }
+
+    #[tokio::test]
+    async fn stdin_imports_resolve_against_filepath() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        fs::write(
+            dir.path().join("lib.wdl"),
+            "version 1.1\n\ntask greet {\n    command <<<>>>\n}\n",
+        )
+        .expect("should write document");
+
+        let stdin = StdinDocument::from_reader(
+            IMPORTING_SOURCE.as_bytes(),
+            Some(&dir.path().join("main.wdl")),
+        )
+        .expect("should stage document");
+        assert!(analyzes_cleanly(&stdin).await);
+
+        // Without a path, the import is resolved against a temporary directory
+        let stdin = StdinDocument::from_reader(IMPORTING_SOURCE.as_bytes(), None)
+            .expect("should stage document");
+        assert!(!analyzes_cleanly(&stdin).await);
+    }
+}
@@ -40,6 +40,52 @@ pub fn list_all_rules() -> String {
     result
 }
 
+/// The maximum number of suggestions to display for an unknown rule.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Finds the IDs of the rules closest to the given (lowercase) name.
+///
+/// The returned IDs are ordered from closest to furthest.
+pub fn find_nearest_rules(name: &str) -> Vec<String> {
+    let threshold = (name.chars().count() / 3).max(2);
+
+    let mut candidates = analysis::rules()
+        .iter()
+        .map(|r| r.id().to_string())
+        .chain(lint::rules().iter().map(|r| r.id().to_string()))
+        .filter_map(|id| {
+            let distance = edit_distance(name, &id.to_lowercase());
+            (distance <= threshold).then_some((distance, id))
+        })
+        .collect::<Vec<_>>();
+
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, id)| id)
+        .collect()
+}
+
 /// Pretty prints a lint rule to a string.
 pub fn pretty_print_lint_rule(rule: &dyn lint::Rule) {
     println!(
@@ -83,7 +129,17 @@ pub fn explain(args: Args) -> anyhow::Result<()> {
                     pretty_print_lint_rule(rule.as_ref());
                 }
                 None => {
-                    println!("{rules}\n", rules = list_all_rules());
+                    let suggestions = find_nearest_rules(&lowercase_name);
+                    if suggestions.is_empty() {
+                        println!("{rules}\n", rules = list_all_rules());
+                    } else {
+                        println!("Did you mean:");
+                        for suggestion in suggestions {
+                            println!("  - {suggestion}", suggestion = suggestion.bold());
+                        }
+                        println!();
+                    }
+
                     bail!("No rule found with the name `{name}`");
                 }
             }
@@ -92,3 +148,32 @@ pub fn explain(args: Args) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("snakecase", "snakecase"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("snakcase", "snakecase"), 1);
+    }
+
+    #[test]
+    fn suggests_nearest_rules() {
+        let suggestions = find_nearest_rules("snakcase");
+        assert_eq!(suggestions.first().map(String::as_str), Some("SnakeCase"));
+
+        let suggestions = find_nearest_rules("unusedimprt");
+        assert_eq!(
+            suggestions.first().map(String::as_str),
+            Some("UnusedImport")
+        );
+        assert!(suggestions.len() <= MAX_SUGGESTIONS);
+
+        assert!(find_nearest_rules("thisisnotarule").is_empty());
+    }
+}
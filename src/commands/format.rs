@@ -3,6 +3,7 @@
 use std::ffi::OsStr;
 use std::fs;
 use std::io::Read;
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -12,8 +13,9 @@ use anyhow::anyhow;
 use anyhow::bail;
 use clap::Parser;
 use colored::Colorize;
+use ignore::WalkBuilder;
 use pretty_assertions::StrComparison;
-use walkdir::WalkDir;
+use wdl::ast::Diagnostic;
 use wdl::ast::Document;
 use wdl::ast::Node;
 use wdl::format::Config;
@@ -24,7 +26,10 @@ use wdl::format::config::MaxLineLength;
 use wdl::format::element::node::AstNodeFormatExt;
 
 use crate::Mode;
+use crate::STDIN_NAME;
+use crate::STDIN_PATH;
 use crate::emit_diagnostics;
+use crate::is_staged_stdin_document;
 
 /// Arguments for the `format` subcommand.
 #[derive(Parser, Debug)]
@@ -35,7 +40,8 @@ use crate::emit_diagnostics;
     after_help = "Use the `--overwrite` option to replace a WDL document or a directory \
                   containing WDL documents with the formatted source.\nUse the `--check` option \
                   to verify that a document or a directory containing WDL documents is already \
-                  formatted and print the diff if not."
+                  formatted and print the diff if not.\nWhen reading from STDIN without \
+                  `--check`, the formatted source is written to STDOUT."
 )]
 pub struct FormatArgs {
     /// The path to the WDL document or a directory containing WDL documents to
@@ -63,14 +69,23 @@ pub struct FormatArgs {
     #[arg(long, value_name = "LENGTH")]
     pub max_line_length: Option<usize>,
 
+    /// The path of the document being read from STDIN.
+    ///
+    /// The path is only used when reporting diagnostics and messages; the
+    /// source is still read from STDIN.
+    #[arg(long, value_name = "PATH")]
+    pub stdin_filepath: Option<PathBuf>,
+
     /// Argument group defining the mode of behavior
     #[command(flatten)]
     mode: ModeGroup,
 }
 
 /// Argument group defining the mode of behavior
+///
+/// One of the modes is required unless reading from STDIN.
 #[derive(Parser, Debug)]
-#[group(required = true, multiple = false)]
+#[group(required = false, multiple = false)]
 pub struct ModeGroup {
     /// Overwrite the WDL documents with the formatted versions
     #[arg(long, conflicts_with = "check")]
@@ -81,67 +96,81 @@ pub struct ModeGroup {
     pub check: bool,
 }
 
-/// Reads source from the given path.
+/// The name of the file used to exclude paths from directory traversal.
 ///
-/// If the path is simply `-`, the source is read from STDIN.
-fn read_source(path: &Path) -> Result<String> {
-    if path.as_os_str() == "-" {
-        let mut source = String::new();
-        std::io::stdin()
-            .read_to_string(&mut source)
-            .context("failed to read source from STDIN")?;
-        Ok(source)
-    } else {
-        Ok(fs::read_to_string(path).with_context(|| {
-            format!("failed to read source file `{path}`", path = path.display())
-        })?)
-    }
+/// The file uses `.gitignore` syntax and may appear in any directory being
+/// traversed.
+const IGNORE_FILENAME: &str = ".sprocketignore";
+
+/// The outcome of formatting a single document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// The document was already formatted.
+    Unchanged,
+    /// The document was not already formatted.
+    ///
+    /// When overwriting, the document has been rewritten with the formatted
+    /// source.
+    Changed,
+    /// The document failed to parse with the given number of diagnostics.
+    Errored(usize),
 }
 
-/// Formats a document.
-///
-/// If `check_only` is true, checks if the document is formatted correctly and
-/// prints the diff if not then exits. Else will format and overwrite the
-/// document.
-///
-/// If the document failed to parse, this emits the diagnostics and returns
-/// `Ok(count)` of the diagnostics to the caller.
-///
-/// A return value of `Ok(0)` indicates the document was formatted.
-fn format_document(
-    config: Config,
-    path: &Path,
-    report_mode: Mode,
-    no_color: bool,
-    check_only: bool,
-) -> Result<usize> {
-    if path.to_str() != Some("-") {
-        let action = if check_only { "checking" } else { "formatting" };
+/// A summary of the outcomes of formatting one or more documents.
+#[derive(Debug, Default)]
+struct Summary {
+    /// The number of documents that were already formatted.
+    unchanged: usize,
+    /// The number of documents that were not already formatted.
+    changed: usize,
+    /// The number of documents that failed to parse.
+    errored: usize,
+    /// The total number of parse diagnostics emitted.
+    diagnostics: usize,
+}
+
+impl Summary {
+    /// Records the outcome of formatting a document.
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Unchanged => self.unchanged += 1,
+            Outcome::Changed => self.changed += 1,
+            Outcome::Errored(count) => {
+                self.errored += 1;
+                self.diagnostics += count;
+            }
+        }
+    }
+
+    /// Prints the summary to STDOUT.
+    fn print(&self, check_only: bool) {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
         println!(
-            "{action_colored} `{path}`",
-            action_colored = if no_color {
-                action.normal()
+            "{changed} file{s1} {action}, {unchanged} file{s2} unchanged, {errored} file{s3} \
+             errored",
+            changed = self.changed,
+            s1 = plural(self.changed),
+            action = if check_only {
+                "would be formatted"
             } else {
-                action.green()
+                "formatted"
             },
-            path = path.display()
+            unchanged = self.unchanged,
+            s2 = plural(self.unchanged),
+            errored = self.errored,
+            s3 = plural(self.errored),
         );
-    } else if !check_only {
-        bail!("cannot overwrite STDIN");
     }
+}
 
-    let source = read_source(path)?;
-    let (document, diagnostics) = Document::parse(&source);
+/// Formats the given source.
+///
+/// If the source failed to parse, the parse diagnostics are returned instead
+/// of the formatted source.
+fn format_source(config: Config, source: &str) -> Result<Result<String, Vec<Diagnostic>>> {
+    let (document, diagnostics) = Document::parse(source);
     if !diagnostics.is_empty() {
-        emit_diagnostics(
-            &diagnostics,
-            &path.to_string_lossy(),
-            &source,
-            report_mode,
-            no_color,
-        );
-
-        return Ok(diagnostics.len());
+        return Ok(Err(diagnostics));
     }
 
     let document = Node::Ast(
@@ -153,22 +182,154 @@ fn format_document(
     .into_format_element();
 
     let formatter = Formatter::new(config);
-    let formatted = formatter.format(&document)?;
+    Ok(Ok(formatter.format(&document)?))
+}
 
-    if check_only {
-        if formatted != source {
-            print!("{}", StrComparison::new(&source, &formatted));
-            return Ok(1);
+/// Formats a document read from the given reader.
+///
+/// If `check_only` is true, checks if the document is formatted correctly and
+/// writes the diff to the writer if not. Else will write the formatted source
+/// to the writer.
+///
+/// The `name` argument is the name reported for the document.
+///
+/// If the document failed to parse, this emits the diagnostics and returns
+/// [`Outcome::Errored`] with the count of the diagnostics to the caller.
+fn format_stdin(
+    config: Config,
+    mut reader: impl Read,
+    mut writer: impl Write,
+    name: &str,
+    report_mode: Mode,
+    no_color: bool,
+    check_only: bool,
+) -> Result<Outcome> {
+    let mut source = String::new();
+    reader
+        .read_to_string(&mut source)
+        .context("failed to read source from STDIN")?;
+
+    let formatted = match format_source(config, &source)? {
+        Ok(formatted) => formatted,
+        Err(diagnostics) => {
+            emit_diagnostics(&diagnostics, name, &source, report_mode, no_color);
+            return Ok(Outcome::Errored(diagnostics.len()));
         }
-        println!("`{path}` is formatted correctly", path = path.display());
-        return Ok(0);
+    };
+
+    if !check_only {
+        write!(writer, "{formatted}").context("failed to write formatted source")?;
+        return Ok(if formatted == source {
+            Outcome::Unchanged
+        } else {
+            Outcome::Changed
+        });
+    }
+
+    if formatted == source {
+        writeln!(writer, "`{name}` is formatted correctly").context("failed to write output")?;
+        return Ok(Outcome::Unchanged);
+    }
+
+    write!(writer, "{}", StrComparison::new(&source, &formatted))
+        .context("failed to write output")?;
+    Ok(Outcome::Changed)
+}
+
+/// Formats a document.
+///
+/// If `check_only` is true, checks if the document is formatted correctly and
+/// prints the diff if not then exits. Else will format and overwrite the
+/// document.
+///
+/// If the document failed to parse, this emits the diagnostics and returns
+/// [`Outcome::Errored`] with the count of the diagnostics to the caller.
+fn format_document(
+    config: Config,
+    path: &Path,
+    report_mode: Mode,
+    no_color: bool,
+    check_only: bool,
+) -> Result<Outcome> {
+    let name = path.to_string_lossy();
+    let action = if check_only { "checking" } else { "formatting" };
+    println!(
+        "{action_colored} `{path}`",
+        action_colored = if no_color {
+            action.normal()
+        } else {
+            action.green()
+        },
+        path = path.display()
+    );
+
+    let source = fs::read_to_string(path)
+        .with_context(|| format!("failed to read source file `{path}`", path = path.display()))?;
+    let formatted = match format_source(config, &source)? {
+        Ok(formatted) => formatted,
+        Err(diagnostics) => {
+            emit_diagnostics(&diagnostics, &name, &source, report_mode, no_color);
+            return Ok(Outcome::Errored(diagnostics.len()));
+        }
+    };
+
+    if formatted == source {
+        if check_only {
+            println!("`{name}` is formatted correctly");
+        }
+        return Ok(Outcome::Unchanged);
+    }
+
+    if check_only {
+        print!("{}", StrComparison::new(&source, &formatted));
+        return Ok(Outcome::Changed);
     }
 
     // write file because check is not true
     fs::write(path, formatted)
         .with_context(|| format!("failed to write `{path}`", path = path.display()))?;
 
-    Ok(0)
+    Ok(Outcome::Changed)
+}
+
+/// Formats every WDL document in a directory, recursively.
+///
+/// Paths excluded by a `.sprocketignore` file are skipped.
+fn format_directory(
+    config: Config,
+    dir: &Path,
+    report_mode: Mode,
+    no_color: bool,
+    check_only: bool,
+) -> Result<Summary> {
+    let walker = WalkBuilder::new(dir)
+        .standard_filters(false)
+        .add_custom_ignore_filename(IGNORE_FILENAME)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+
+    let mut summary = Summary::default();
+    for entry in walker {
+        let entry = entry
+            .with_context(|| format!("failed to walk directory `{dir}`", dir = dir.display()))?;
+        let path = entry.path();
+        if !path.is_file()
+            || path.extension().and_then(OsStr::to_str) != Some("wdl")
+            || is_staged_stdin_document(path)
+        {
+            continue;
+        }
+
+        summary.record(format_document(
+            config,
+            path,
+            report_mode,
+            no_color,
+            check_only,
+        )?);
+    }
+
+    Ok(summary)
 }
 
 /// Runs the `format` command.
@@ -189,44 +350,195 @@ pub fn format(args: FormatArgs) -> Result<()> {
         .max_line_length(max_line_length)
         .build();
 
-    let mut diagnostics = 0;
-    if args.path.to_str() != Some("-") && args.path.is_dir() {
-        for entry in WalkDir::new(&args.path) {
-            let entry = entry.with_context(|| {
-                format!(
-                    "failed to walk directory `{path}`",
-                    path = args.path.display()
-                )
-            })?;
-            let path = entry.path();
-            if !path.is_file() || path.extension().and_then(OsStr::to_str) != Some("wdl") {
-                continue;
-            }
+    let stdin = args.path.as_os_str() == STDIN_PATH;
+    if stdin && args.mode.overwrite {
+        bail!("cannot overwrite STDIN");
+    } else if !stdin && !args.mode.overwrite && !args.mode.check {
+        bail!("one of `--overwrite` or `--check` is required unless formatting STDIN");
+    } else if !stdin && args.stdin_filepath.is_some() {
+        bail!("`--stdin-filepath` requires the document to be read from STDIN");
+    }
 
-            diagnostics += format_document(
-                config,
-                path,
-                args.report_mode,
-                args.no_color,
-                args.mode.check,
-            )?;
-        }
-    } else {
-        diagnostics += format_document(
+    let mut summary = Summary::default();
+    if !stdin && args.path.is_dir() {
+        summary = format_directory(
             config,
             &args.path,
             args.report_mode,
             args.no_color,
             args.mode.check,
         )?;
+        summary.print(args.mode.check);
+    } else if stdin {
+        let name = match &args.stdin_filepath {
+            Some(path) => path.to_string_lossy(),
+            None => STDIN_NAME.into(),
+        };
+        summary.record(format_stdin(
+            config,
+            std::io::stdin().lock(),
+            std::io::stdout().lock(),
+            &name,
+            args.report_mode,
+            args.no_color,
+            args.mode.check,
+        )?);
+    } else {
+        summary.record(format_document(
+            config,
+            &args.path,
+            args.report_mode,
+            args.no_color,
+            args.mode.check,
+        )?);
     }
 
-    if diagnostics > 0 {
+    if summary.diagnostics > 0 {
         bail!(
             "aborting due to previous {diagnostics} diagnostic{s}",
-            s = if diagnostics == 1 { "" } else { "s" }
+            diagnostics = summary.diagnostics,
+            s = if summary.diagnostics == 1 { "" } else { "s" }
+        );
+    }
+
+    if args.mode.check && summary.changed > 0 {
+        bail!(
+            "{changed} file{s} not formatted correctly",
+            changed = summary.changed,
+            s = if summary.changed == 1 { " is" } else { "s are" }
         );
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A document that is not formatted.
+    const UNFORMATTED: &str = "version 1.1\n\nworkflow   test {\n}\n";
+
+    #[test]
+    fn format_stdin_source() {
+        let config = Builder::default().build();
+
+        let formatted = format_source(config, UNFORMATTED)
+            .expect("should format source")
+            .expect("source should parse");
+        assert_ne!(formatted, UNFORMATTED);
+        assert_eq!(
+            format_source(config, &formatted)
+                .expect("should format source")
+                .expect("source should parse"),
+            formatted
+        );
+
+        let diagnostics = format_source(config, "version 1.1\n\nworkflow {\n")
+            .expect("should format source")
+            .expect_err("source should not parse");
+        assert!(!diagnostics.is_empty());
+    }
+
+    #[test]
+    fn format_stdin_with_filepath() {
+        let config = Builder::default().build();
+        let source = "version 1.2\n\nworkflow   test {\n}\n";
+
+        // The formatted source is written to the writer
+        let mut output = Vec::new();
+        let outcome = format_stdin(
+            config,
+            source.as_bytes(),
+            &mut output,
+            "dir/test.wdl",
+            Mode::OneLine,
+            true,
+            false,
+        )
+        .expect("should format document");
+        assert_eq!(outcome, Outcome::Changed);
+        let formatted = String::from_utf8(output).expect("output should be UTF-8");
+        assert!(formatted.starts_with("version 1.2\n"));
+        assert!(formatted.contains("workflow test {"));
+
+        // Checking the formatted source reports it by the given filepath
+        let mut output = Vec::new();
+        let outcome = format_stdin(
+            config,
+            formatted.as_bytes(),
+            &mut output,
+            "dir/test.wdl",
+            Mode::OneLine,
+            true,
+            true,
+        )
+        .expect("should check document");
+        assert_eq!(outcome, Outcome::Unchanged);
+        assert_eq!(
+            String::from_utf8(output).expect("output should be UTF-8"),
+            "`dir/test.wdl` is formatted correctly\n"
+        );
+    }
+
+    #[test]
+    fn format_directory_summary() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        let config = Builder::default().build();
+
+        // Formatting a document once leaves it formatted for the next pass
+        fs::write(dir.path().join("a.wdl"), UNFORMATTED).expect("should write file");
+        let summary = format_directory(config, dir.path(), Mode::OneLine, true, false)
+            .expect("should format directory");
+        assert_eq!(summary.changed, 1);
+        assert_eq!(summary.unchanged, 0);
+
+        fs::write(dir.path().join("b.wdl"), UNFORMATTED).expect("should write file");
+        let summary = format_directory(config, dir.path(), Mode::OneLine, true, true)
+            .expect("should check directory");
+        assert_eq!(summary.changed, 1);
+        assert_eq!(summary.unchanged, 1);
+        assert_eq!(summary.errored, 0);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("b.wdl")).expect("should read file"),
+            UNFORMATTED,
+            "checking should not modify the document"
+        );
+    }
+
+    #[test]
+    fn format_directory_respects_ignore_file() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        fs::write(dir.path().join(IGNORE_FILENAME), "ignored.wdl\n").expect("should write file");
+        fs::write(dir.path().join("ignored.wdl"), UNFORMATTED).expect("should write file");
+
+        let summary = format_directory(
+            Builder::default().build(),
+            dir.path(),
+            Mode::OneLine,
+            true,
+            true,
+        )
+        .expect("should check directory");
+        assert_eq!(summary.changed, 0);
+        assert_eq!(summary.unchanged, 0);
+    }
+
+    #[test]
+    fn format_directory_skips_staged_stdin_documents() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        fs::write(dir.path().join(".sprocket-stdin-abc.wdl"), UNFORMATTED)
+            .expect("should write file");
+
+        let summary = format_directory(
+            Builder::default().build(),
+            dir.path(),
+            Mode::OneLine,
+            true,
+            true,
+        )
+        .expect("should check directory");
+        assert_eq!(summary.changed, 0);
+        assert_eq!(summary.unchanged, 0);
+    }
+}
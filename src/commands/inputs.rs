@@ -0,0 +1,329 @@
+//! Implementation of the `inputs` command.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::Parser;
+use indexmap::IndexMap;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use url::Url;
+use wdl::analysis::document::Document;
+use wdl::analysis::document::Input;
+use wdl::analysis::path_to_uri;
+use wdl::analysis::types::CompoundType;
+use wdl::analysis::types::Optional;
+use wdl::analysis::types::PrimitiveType;
+use wdl::analysis::types::Type;
+use wdl::cli::analyze;
+
+/// The JSON Schema dialect of generated schemas.
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Arguments for the `inputs` command.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+pub struct InputsArgs {
+    /// The path or URL to the WDL document.
+    #[arg(required = true)]
+    #[clap(value_name = "PATH or URL")]
+    pub document: String,
+
+    /// The name of the task or workflow to describe.
+    ///
+    /// Defaults to the workflow in the document or, if there is no workflow,
+    /// the only task in the document.
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Prints a JSON Schema describing a valid input file.
+    #[arg(long, required = true)]
+    pub schema: bool,
+}
+
+/// Converts a WDL type to a JSON Schema.
+///
+/// The definitions of any structs referenced by the type are added to `defs`.
+fn type_to_schema(ty: &Type, defs: &mut Map<String, Value>) -> Value {
+    let schema = match ty {
+        Type::Primitive(ty, _) => match ty {
+            PrimitiveType::Boolean => json!({ "type": "boolean" }),
+            PrimitiveType::Integer => json!({ "type": "integer" }),
+            PrimitiveType::Float => json!({ "type": "number" }),
+            PrimitiveType::String | PrimitiveType::File | PrimitiveType::Directory => {
+                json!({ "type": "string" })
+            }
+        },
+        Type::Compound(ty, _) => match ty {
+            CompoundType::Array(ty) => {
+                let mut schema = json!({
+                    "type": "array",
+                    "items": type_to_schema(ty.element_type(), defs),
+                });
+                if ty.is_non_empty() {
+                    schema["minItems"] = 1.into();
+                }
+
+                schema
+            }
+            CompoundType::Pair(ty) => json!({
+                "type": "object",
+                "properties": {
+                    "left": type_to_schema(ty.left_type(), defs),
+                    "right": type_to_schema(ty.right_type(), defs),
+                },
+                "required": ["left", "right"],
+                "additionalProperties": false,
+            }),
+            // Map keys are always strings in JSON, so only the values are described
+            CompoundType::Map(ty) => json!({
+                "type": "object",
+                "additionalProperties": type_to_schema(ty.value_type(), defs),
+            }),
+            CompoundType::Struct(ty) => {
+                let name = ty.name().to_string();
+                if !defs.contains_key(&name) {
+                    // Reserve the definition first in case the struct is recursive
+                    defs.insert(name.clone(), Value::Null);
+                    let properties = ty
+                        .members()
+                        .iter()
+                        .map(|(member, ty)| (member.clone(), type_to_schema(ty, defs)))
+                        .collect::<Map<_, _>>();
+                    let required = ty
+                        .members()
+                        .iter()
+                        .filter(|(_, ty)| !ty.is_optional())
+                        .map(|(member, _)| member.as_str())
+                        .collect::<Vec<_>>();
+                    defs.insert(
+                        name.clone(),
+                        json!({
+                            "type": "object",
+                            "properties": properties,
+                            "required": required,
+                            "additionalProperties": false,
+                        }),
+                    );
+                }
+
+                json!({ "$ref": format!("#/$defs/{name}") })
+            }
+        },
+        Type::Object | Type::OptionalObject => json!({ "type": "object" }),
+        // The remaining types cannot be the type of an input
+        _ => json!({}),
+    };
+
+    if ty.is_optional() {
+        json!({ "anyOf": [schema, { "type": "null" }] })
+    } else {
+        schema
+    }
+}
+
+/// Creates a JSON Schema for an input file of the given task or workflow.
+fn inputs_to_schema(name: &str, inputs: &IndexMap<String, Input>) -> Value {
+    let mut defs = Map::new();
+    let properties = inputs
+        .iter()
+        .map(|(input, i)| (format!("{name}.{input}"), type_to_schema(i.ty(), &mut defs)))
+        .collect::<Map<_, _>>();
+    let required = inputs
+        .iter()
+        .filter(|(_, i)| i.required())
+        .map(|(input, _)| format!("{name}.{input}"))
+        .collect::<Vec<_>>();
+
+    let mut schema = json!({
+        "$schema": SCHEMA_DIALECT,
+        "title": name,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    });
+    if !defs.is_empty() {
+        schema["$defs"] = defs.into();
+    }
+
+    schema
+}
+
+/// Creates a JSON Schema for an input file of a task or workflow in the given
+/// document.
+///
+/// If `name` is not specified, the document's workflow is used; if the
+/// document has no workflow, it must contain exactly one task.
+pub fn input_schema(document: &Document, name: Option<&str>) -> Result<Value> {
+    if let Some(name) = name {
+        if let Some(workflow) = document.workflow().filter(|w| w.name() == name) {
+            return Ok(inputs_to_schema(workflow.name(), workflow.inputs()));
+        }
+
+        if let Some(task) = document.tasks().find(|t| t.name() == name) {
+            return Ok(inputs_to_schema(task.name(), task.inputs()));
+        }
+
+        bail!("document does not contain a task or workflow named `{name}`");
+    }
+
+    if let Some(workflow) = document.workflow() {
+        return Ok(inputs_to_schema(workflow.name(), workflow.inputs()));
+    }
+
+    let mut tasks = document.tasks();
+    match (tasks.next(), tasks.next()) {
+        (Some(task), None) => Ok(inputs_to_schema(task.name(), task.inputs())),
+        (None, _) => bail!("document does not contain a task or workflow"),
+        _ => bail!(
+            "document contains multiple tasks: specify one of {names} with `--name`",
+            names = document
+                .tasks()
+                .map(|t| format!("`{name}`", name = t.name()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Analyzes the given document and creates a JSON Schema for its inputs.
+async fn schema(document: &str, name: Option<&str>) -> Result<Value> {
+    // Normalize local paths the same way analysis does so the results match
+    let uri = match Url::parse(document) {
+        Ok(uri) => uri,
+        Err(_) => path_to_uri(document)
+            .with_context(|| format!("failed to determine the URI of `{document}`"))?,
+    };
+
+    let results = analyze(document, Vec::new(), false, false).await?;
+    let Some(result) = results
+        .iter()
+        .find(|r| r.document().uri().as_str() == uri.as_str())
+    else {
+        bail!("failed to find the analysis results for `{document}`");
+    };
+
+    if let Some(e) = result.error() {
+        bail!("failed to analyze `{uri}`: {e:#}");
+    }
+
+    input_schema(result.document(), name)
+}
+
+/// Runs the `inputs` command.
+pub async fn inputs(args: InputsArgs) -> Result<()> {
+    let schema = schema(&args.document, args.name.as_deref()).await?;
+    println!(
+        "{json}",
+        json = serde_json::to_string_pretty(&schema).context("failed to serialize schema")?
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A document with inputs of every kind of type.
+    const SOURCE: &str = r#"version 1.1
+
+struct Sample {
+    String name
+    Array[File]+ reads
+    Int? lane
+}
+
+workflow test {
+    input {
+        Array[Sample] samples
+        Map[String, Pair[Int, Float]] weights
+        Boolean? verbose
+        String prefix = "out"
+    }
+}
+"#;
+
+    #[tokio::test]
+    async fn schema_validates_inputs() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        let path = dir.path().join("test.wdl");
+        std::fs::write(&path, SOURCE).expect("should write document");
+
+        let schema = schema(path.to_str().unwrap(), None)
+            .await
+            .expect("should create schema");
+        assert_eq!(schema["required"], json!(["test.samples", "test.weights"]));
+        assert_eq!(
+            schema["$defs"]["Sample"]["required"],
+            json!(["name", "reads"])
+        );
+
+        let validator = jsonschema::validator_for(&schema).expect("schema should be valid");
+        assert!(validator.is_valid(&json!({
+            "test.samples": [{ "name": "a", "reads": ["a.fastq"], "lane": null }],
+            "test.weights": { "a": { "left": 1, "right": 0.5 } },
+            "test.verbose": true,
+        })));
+
+        // Missing required input
+        assert!(!validator.is_valid(&json!({ "test.samples": [] })));
+
+        // Empty non-empty array
+        assert!(!validator.is_valid(&json!({
+            "test.samples": [{ "name": "a", "reads": [] }],
+            "test.weights": {},
+        })));
+
+        // Unknown input
+        assert!(!validator.is_valid(&json!({
+            "test.samples": [],
+            "test.weights": {},
+            "test.unknown": 1,
+        })));
+    }
+
+    #[tokio::test]
+    async fn schema_requires_name_for_multiple_tasks() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        let path = dir.path().join("test.wdl");
+        std::fs::write(
+            &path,
+            "version 1.1\n\ntask a {\n    command <<<>>>\n}\n\ntask b {\n    input {\n        \
+             Int x\n    }\n\n    command <<<>>>\n}\n",
+        )
+        .expect("should write document");
+
+        // The path is not normalized, but must still match the analyzed document
+        let path = dir.path().join(".").join("test.wdl");
+        let path = path.to_str().unwrap();
+        let e = schema(path, None).await.expect_err("should require a name");
+        assert!(e.to_string().contains("`a`, `b`"));
+
+        let schema = schema(path, Some("b")).await.expect("should create schema");
+        assert_eq!(schema["required"], json!(["b.x"]));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn schema_through_symlink() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        let target = dir.path().join("target");
+        std::fs::create_dir(&target).expect("should create directory");
+        std::fs::write(
+            target.join("test.wdl"),
+            "version 1.1\n\nworkflow test {\n    input {\n        Int x\n    }\n}\n",
+        )
+        .expect("should write document");
+
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&target, &link).expect("should create symlink");
+
+        let schema = schema(link.join("test.wdl").to_str().unwrap(), None)
+            .await
+            .expect("should create schema");
+        assert_eq!(schema["required"], json!(["test.x"]));
+    }
+}
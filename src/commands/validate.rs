@@ -7,17 +7,33 @@ use clap::Parser;
 use wdl::cli::validate_inputs as wdl_validate_inputs;
 
 use crate::Mode;
+use crate::STDIN_PATH;
+use crate::StdinDocument;
 use crate::emit_diagnostics;
 
 /// Arguments for the `validate-inputs` command.
 #[derive(Parser, Debug)]
 #[command(author, version, about)]
 pub struct ValidateInputsArgs {
-    /// The path or URL to the WDL document.
+    /// The path or URL to the WDL document (`-` for STDIN).
+    ///
+    /// Relative imports in a document read from STDIN are resolved against
+    /// the directory of `--stdin-filepath` or, if it is not specified, a
+    /// temporary directory.
     #[arg(required = true)]
     #[clap(value_name = "PATH or URL")]
     pub document: String,
 
+    /// The path of the document being read from STDIN.
+    ///
+    /// The path is used when reporting diagnostics and to resolve relative
+    /// imports; the source is still read from STDIN. To resolve imports, the
+    /// source is written to a hidden `.sprocket-stdin-*.wdl` file in the
+    /// path's directory that is removed on exit; directory walks skip such
+    /// files if they are left behind.
+    #[arg(long, value_name = "PATH")]
+    pub stdin_filepath: Option<PathBuf>,
+
     /// The path to the input JSON file.
     #[arg(short, long, value_name = "JSON")]
     pub inputs: PathBuf,
@@ -38,11 +54,30 @@ pub struct ValidateInputsArgs {
 /// * No extraneous inputs are provided.
 /// * Any provided `File` or `Directory` inputs exist.
 pub async fn validate_inputs(args: ValidateInputsArgs) -> Result<()> {
-    if let Some(diagnostic) = wdl_validate_inputs(&args.document, &args.inputs).await? {
-        let source = std::fs::read_to_string(&args.document)?;
+    let stdin = if args.document == STDIN_PATH {
+        Some(StdinDocument::read(args.stdin_filepath.as_deref())?)
+    } else if args.stdin_filepath.is_some() {
+        anyhow::bail!("`--stdin-filepath` requires the document to be read from STDIN");
+    } else {
+        None
+    };
+
+    let document = match &stdin {
+        Some(stdin) => stdin.path().to_string_lossy().into_owned(),
+        None => args.document.clone(),
+    };
+
+    if let Some(diagnostic) = wdl_validate_inputs(&document, &args.inputs).await? {
+        let (name, source) = match &stdin {
+            Some(stdin) => (stdin.name(), stdin.source().to_string()),
+            None => (
+                args.document.as_str(),
+                std::fs::read_to_string(&args.document)?,
+            ),
+        };
         emit_diagnostics(
             &[diagnostic],
-            &args.document,
+            name,
             &source,
             args.report_mode,
             args.no_color,
@@ -52,3 +87,35 @@ pub async fn validate_inputs(args: ValidateInputsArgs) -> Result<()> {
     println!("All inputs are valid");
     anyhow::Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn stdin_imports_resolve_against_filepath() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        std::fs::write(
+            dir.path().join("lib.wdl"),
+            "version 1.1\n\ntask greet {\n    input {\n        String name\n    }\n\n    \
+             command <<<>>>\n}\n",
+        )
+        .expect("should write document");
+        let inputs = dir.path().join("inputs.json");
+        std::fs::write(&inputs, r#"{ "test.name": "world" }"#).expect("should write inputs");
+
+        let source = "version 1.1\n\nimport \"lib.wdl\"\n\nworkflow test {\n    input {\n        \
+                      String name\n    }\n\n    call lib.greet { input: name }\n}\n";
+        let stdin =
+            StdinDocument::from_reader(source.as_bytes(), Some(&dir.path().join("main.wdl")))
+                .expect("should stage document");
+
+        let diagnostic = wdl_validate_inputs(&stdin.path().to_string_lossy(), &inputs)
+            .await
+            .expect("should validate inputs");
+        assert!(
+            diagnostic.is_none(),
+            "unexpected diagnostic: {diagnostic:?}"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Implementation of the version command.
+
+use anyhow::Result;
+use clap::Parser;
+use serde_json::Value;
+use serde_json::json;
+
+/// The versions of the `wdl` component crates Sprocket was built with.
+///
+/// This is a comma-separated list of `name=version` pairs recorded by the
+/// build script; it is empty if the versions were not recorded.
+const COMPONENT_VERSIONS: &str = match option_env!("SPROCKET_COMPONENT_VERSIONS") {
+    Some(versions) => versions,
+    None => "",
+};
+
+/// Arguments for the `version` subcommand.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+pub struct VersionArgs {
+    /// Prints the versions as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Gets the names and versions of the `wdl` component crates Sprocket was
+/// built with.
+///
+/// The components are ordered by name.
+pub fn component_versions() -> impl Iterator<Item = (&'static str, &'static str)> {
+    COMPONENT_VERSIONS
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+}
+
+/// Gets the versions of Sprocket and its components as JSON.
+///
+/// The components are `null` if their versions were not recorded when
+/// Sprocket was built.
+pub fn version_json(build: &str) -> Value {
+    let components = component_versions()
+        .map(|(name, version)| (name.to_string(), version.into()))
+        .collect::<serde_json::Map<_, _>>();
+
+    json!({
+        "sprocket": env!("CARGO_PKG_VERSION"),
+        "build": build,
+        "components": if components.is_empty() { Value::Null } else { components.into() },
+    })
+}
+
+/// Runs the `version` command.
+///
+/// The `build` argument is the rendered description of the Sprocket build.
+pub fn version(args: VersionArgs, build: &str) -> Result<()> {
+    if args.json {
+        println!(
+            "{json}",
+            json = serde_json::to_string_pretty(&version_json(build))?
+        );
+    } else {
+        println!("sprocket {build}");
+        let mut components = component_versions().peekable();
+        if components.peek().is_none() {
+            println!("  (component versions were not recorded for this build)");
+        }
+
+        for (name, version) in components {
+            println!("  {name} {version}");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_versions() {
+        let json = version_json("build");
+        assert_eq!(json["sprocket"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["build"], "build");
+        assert!(json["components"]["wdl"].is_string());
+        assert!(json["components"]["wdl-analysis"].is_string());
+    }
+}
@@ -7,6 +7,13 @@
 #![warn(clippy::missing_docs_in_private_items)]
 #![warn(rustdoc::broken_intra_doc_links)]
 
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
 use clap::ValueEnum;
 use codespan_reporting::files::SimpleFile;
 use codespan_reporting::term::Config;
@@ -14,10 +21,164 @@ use codespan_reporting::term::DisplayStyle;
 use codespan_reporting::term::emit;
 use codespan_reporting::term::termcolor::ColorChoice;
 use codespan_reporting::term::termcolor::StandardStream;
+use tempfile::NamedTempFile;
+use tempfile::TempPath;
+use wdl::analysis::path_to_uri;
 use wdl::ast::Diagnostic;
 
+pub mod baseline;
 pub mod commands;
 
+/// The path argument that indicates a document should be read from STDIN.
+pub const STDIN_PATH: &str = "-";
+
+/// The name used when reporting diagnostics for a document read from STDIN.
+pub const STDIN_NAME: &str = "<stdin>";
+
+/// The file name prefix of documents read from STDIN that are staged on disk.
+///
+/// Staged documents are normally removed on exit, but may be left behind if
+/// the process is killed; directory walks skip files with this prefix.
+pub const STDIN_FILE_PREFIX: &str = ".sprocket-stdin-";
+
+/// Determines if the given path is a document read from STDIN that was staged
+/// on disk.
+pub fn is_staged_stdin_document(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with(STDIN_FILE_PREFIX))
+}
+
+/// Creates a hidden temporary file for a document read from STDIN in the given
+/// directory.
+fn create_staged_file(dir: &Path) -> Result<NamedTempFile> {
+    tempfile::Builder::new()
+        .prefix(STDIN_FILE_PREFIX)
+        .suffix(".wdl")
+        .tempfile_in(dir)
+        .with_context(|| {
+            format!(
+                "failed to create temporary file in `{dir}`",
+                dir = dir.display()
+            )
+        })
+}
+
+/// Normalizes a local path the same way analysis normalizes document paths.
+fn normalize_path(path: &Path) -> Result<PathBuf> {
+    path_to_uri(path)
+        .and_then(|uri| uri.to_file_path().ok())
+        .with_context(|| {
+            format!(
+                "failed to determine the absolute path of `{path}`",
+                path = path.display()
+            )
+        })
+}
+
+/// Represents a WDL document read from STDIN.
+///
+/// Analysis operates on paths, so the source is written to a hidden temporary
+/// file that is removed when this value is dropped.
+///
+/// Relative imports in the document are resolved against the directory of the
+/// temporary file. If the path the document was read from is known, the file
+/// is created in the same directory so that its imports resolve as they
+/// would for the original document; otherwise, or if the directory is not
+/// writable, it is created in the system's temporary directory.
+#[derive(Debug)]
+pub struct StdinDocument {
+    /// The temporary file containing the document.
+    _file: TempPath,
+    /// The path to the staged document.
+    path: PathBuf,
+    /// The path of the original document, if known.
+    original_path: Option<PathBuf>,
+    /// The name to use for the document when reporting diagnostics.
+    name: String,
+    /// The source of the document.
+    source: String,
+}
+
+impl StdinDocument {
+    /// Reads a document from STDIN.
+    ///
+    /// The `filepath` argument is the path the document was read from, if
+    /// known.
+    pub fn read(filepath: Option<&Path>) -> Result<Self> {
+        Self::from_reader(std::io::stdin(), filepath)
+    }
+
+    /// Reads a document from the given reader.
+    ///
+    /// The `filepath` argument is the path the document was read from, if
+    /// known.
+    pub fn from_reader(mut reader: impl Read, filepath: Option<&Path>) -> Result<Self> {
+        let mut source = String::new();
+        reader
+            .read_to_string(&mut source)
+            .context("failed to read source from STDIN")?;
+
+        let dir = filepath.map(|path| {
+            path.parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or(Path::new("."))
+        });
+
+        let mut file = match dir.map(create_staged_file) {
+            Some(Ok(file)) => file,
+            Some(Err(e)) => {
+                eprintln!(
+                    "warning: {e:#}; relative imports will be resolved against a temporary \
+                     directory"
+                );
+                create_staged_file(&std::env::temp_dir())?
+            }
+            None => create_staged_file(&std::env::temp_dir())?,
+        };
+        file.write_all(source.as_bytes()).with_context(|| {
+            format!(
+                "failed to write source from STDIN to `{path}`",
+                path = file.path().display()
+            )
+        })?;
+
+        let file = file.into_temp_path();
+        let path = normalize_path(&file)?;
+        let original_path = filepath.map(normalize_path).transpose()?;
+
+        Ok(Self {
+            _file: file,
+            path,
+            original_path,
+            name: filepath
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_else(|| STDIN_NAME.to_string()),
+            source,
+        })
+    }
+
+    /// Gets the path to the staged document.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the absolute path of the original document, if known.
+    pub fn original_path(&self) -> Option<&Path> {
+        self.original_path.as_deref()
+    }
+
+    /// Gets the name to use for the document when reporting diagnostics.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the source of the document.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
 /// The diagnostic mode to use for reporting diagnostics.
 #[derive(Clone, Copy, Debug, Default, ValueEnum, PartialEq, Eq)]
 pub enum Mode {
@@ -77,3 +238,52 @@ fn emit_diagnostics<'a>(
         emit(&mut writer, &config, &file, &diagnostic.to_codespan()).unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stdin_document_without_path() {
+        let document = StdinDocument::from_reader("version 1.1\n".as_bytes(), None)
+            .expect("should stage document");
+        assert_eq!(document.name(), STDIN_NAME);
+        assert_eq!(document.source(), "version 1.1\n");
+        assert_eq!(document.original_path(), None);
+        assert_eq!(
+            std::fs::read_to_string(document.path()).expect("should read staged document"),
+            "version 1.1\n"
+        );
+
+        let path = document.path().to_path_buf();
+        drop(document);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn stdin_document_with_path() {
+        let dir = tempfile::tempdir().expect("should create temporary directory");
+        let filepath = dir.path().join(".").join("test.wdl");
+
+        let document = StdinDocument::from_reader("version 1.1\n".as_bytes(), Some(&filepath))
+            .expect("should stage document");
+        assert_eq!(document.name(), filepath.to_string_lossy());
+
+        // Paths are normalized the same way as analyzed documents
+        let dir = normalize_path(dir.path()).expect("should normalize");
+        assert_eq!(
+            document.original_path(),
+            Some(dir.join("test.wdl").as_path())
+        );
+
+        // The document is staged next to the original so imports resolve
+        assert_eq!(document.path().parent(), Some(dir.as_path()));
+        assert!(is_staged_stdin_document(document.path()));
+        assert_eq!(
+            path_to_uri(document.path())
+                .and_then(|uri| uri.to_file_path().ok())
+                .as_deref(),
+            Some(document.path())
+        );
+    }
+}
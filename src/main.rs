@@ -34,6 +34,9 @@ enum Commands {
     #[clap(alias = "fmt")]
     Format(commands::format::FormatArgs),
 
+    /// Describes the inputs of a task or workflow.
+    Inputs(commands::inputs::InputsArgs),
+
     /// Validates an input JSON file against a task or workflow input schema.
     ///
     /// This ensures that every required input is supplied, every supplied input
@@ -42,7 +45,11 @@ enum Commands {
     ///
     /// It will not catch potential runtime errors that
     /// may occur when running the task or workflow.
+    #[clap(alias = "validate")]
     ValidateInputs(commands::validate::ValidateInputsArgs),
+
+    /// Displays the version of Sprocket and its WDL components.
+    Version(commands::version::VersionArgs),
 }
 
 #[derive(Parser)]
@@ -68,12 +75,20 @@ pub async fn inner() -> anyhow::Result<()> {
     tracing::subscriber::set_global_default(subscriber)?;
 
     match cli.command {
-        Commands::Check(args) => commands::check::check(args).await,
-        Commands::Lint(args) => commands::check::lint(args).await,
+        Commands::Check(mut args) => {
+            args.common.summary_only |= cli.verbose.is_silent();
+            commands::check::check(args).await
+        }
+        Commands::Lint(mut args) => {
+            args.common.summary_only |= cli.verbose.is_silent();
+            commands::check::lint(args).await
+        }
         Commands::Explain(args) => commands::explain::explain(args),
         Commands::Analyzer(args) => commands::analyzer::analyzer(args).await,
         Commands::Format(args) => commands::format::format(args),
+        Commands::Inputs(args) => commands::inputs::inputs(args).await,
         Commands::ValidateInputs(args) => commands::validate::validate_inputs(args).await,
+        Commands::Version(args) => commands::version::version(args, &render_testament!(TESTAMENT)),
     }
 }
 